@@ -8,6 +8,44 @@ use std::io::Write;
 use rand::Rng;
 use sysinfo::System;
 use battery;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tauri::Emitter;
+
+/// Live progress for an in-flight sanitization, emitted as `sanitize-progress`
+/// so the UI can show a bar, ETA, and throughput instead of waiting on a single
+/// final string.
+#[derive(Clone, serde::Serialize)]
+struct SanitizeProgress {
+    drive: String,
+    bytes_written: u64,
+    total_bytes: u64,
+    pass_index: u32,
+    pass_total: u32,
+    percent: f64,
+    mb_per_sec: f64,
+}
+
+/// Per-drive cancellation flags shared between `sanitize_drive` and
+/// `cancel_sanitize`. Checked between block writes so a wipe stops cleanly.
+fn cancel_registry() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Fetch (or create) the cancellation flag for a drive, clearing any stale
+/// signal from a previous run.
+fn cancel_token(drive: &str) -> Arc<AtomicBool> {
+    let mut registry = cancel_registry().lock().unwrap();
+    let token = registry
+        .entry(drive.to_string())
+        .or_insert_with(|| Arc::new(AtomicBool::new(false)))
+        .clone();
+    token.store(false, Ordering::SeqCst);
+    token
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct DriveInfo {
@@ -18,6 +56,280 @@ struct DriveInfo {
     file_system: String,
     serial_number: u32,
     is_system: bool,
+    // "SSD" / "HDD" / "Removable" / "Unknown". Flash media remap blocks via
+    // wear-leveling, so an overwrite leaves old data on spare cells; the
+    // sanitizer uses this to pick overwrite vs. TRIM/secure-erase.
+    disk_kind: String,
+    // "Fixed" / "Removable" / "Unknown" from GetDriveTypeW (Windows). Lets the
+    // UI distinguish removable media and letterless volumes from fixed disks.
+    drive_type: String,
+    // Device model string, so the erasure certificate can identify the exact
+    // hardware that was sanitized.
+    model: String,
+}
+
+/// Resolve a Linux block-device node (possibly a partition like `/dev/sda1` or
+/// `/dev/nvme0n1p2`) to its parent whole-disk name (`sda`, `nvme0n1`). The
+/// sysfs entries for `queue/rotational` and `device/model` live only on the
+/// whole disk, so a partition node must be walked up first.
+#[cfg(target_os = "linux")]
+fn linux_parent_block_device(dev: &str) -> String {
+    let name = dev.trim_end_matches('/').rsplit('/').next().unwrap_or(dev);
+
+    // A whole disk already has its own /sys/block entry.
+    if Path::new(&format!("/sys/block/{}", name)).exists() {
+        return name.to_string();
+    }
+
+    // A partition's /sys/class/block symlink points at .../block/<disk>/<part>,
+    // so its parent directory name is the whole disk.
+    if let Ok(link) = fs::read_link(format!("/sys/class/block/{}", name)) {
+        if let Some(parent) = link
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+        {
+            if !parent.is_empty() && parent != "block" {
+                return parent.to_string();
+            }
+        }
+    }
+
+    // Fallback string heuristic: strip an `nvme0n1p2`/`mmcblk0p1` partition
+    // suffix, otherwise a trailing `sda1`-style digit run.
+    if let Some(idx) = name.rfind('p') {
+        let (head, tail) = name.split_at(idx);
+        if !tail[1..].is_empty()
+            && tail[1..].chars().all(|c| c.is_ascii_digit())
+            && head.chars().last().is_some_and(|c| c.is_ascii_digit())
+        {
+            return head.to_string();
+        }
+    }
+    name.trim_end_matches(|c: char| c.is_ascii_digit()).to_string()
+}
+
+/// Best-effort device model string for certificate/device-identity purposes.
+///
+/// Windows reads the `StorageDeviceProperty` product id, macOS the diskutil
+/// media name, and Linux `/sys/block/<dev>/device/model`.
+fn detect_model(letter: &str) -> String {
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, HANDLE};
+        use windows::Win32::Storage::FileSystem::{
+            CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+            OPEN_EXISTING,
+        };
+        use windows::Win32::System::Ioctl::{
+            IOCTL_STORAGE_QUERY_PROPERTY, PropertyStandardQuery, STORAGE_DEVICE_DESCRIPTOR,
+            STORAGE_PROPERTY_QUERY, StorageDeviceProperty,
+        };
+        use windows::Win32::System::IO::DeviceIoControl;
+        use windows::core::{HSTRING, PCWSTR};
+
+        let drive = letter.trim_end_matches(['\\', '/']);
+        let device_path = HSTRING::from(format!("\\\\.\\{}", drive));
+
+        unsafe {
+            let handle: HANDLE = match CreateFileW(
+                PCWSTR::from_raw(device_path.as_ptr()),
+                GENERIC_READ.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                HANDLE::default(),
+            ) {
+                Ok(h) => h,
+                Err(_) => return String::new(),
+            };
+
+            let query = STORAGE_PROPERTY_QUERY {
+                PropertyId: StorageDeviceProperty,
+                QueryType: PropertyStandardQuery,
+                AdditionalParameters: [0],
+            };
+            // The descriptor is variable length; over-allocate and read the
+            // product id at the offset the header reports.
+            let mut buffer = [0u8; 1024];
+            let mut returned = 0u32;
+            let ok = DeviceIoControl(
+                handle,
+                IOCTL_STORAGE_QUERY_PROPERTY,
+                Some(&query as *const _ as *const _),
+                std::mem::size_of::<STORAGE_PROPERTY_QUERY>() as u32,
+                Some(buffer.as_mut_ptr() as *mut _),
+                buffer.len() as u32,
+                Some(&mut returned),
+                None,
+            );
+            let _ = CloseHandle(handle);
+            if ok.is_err() {
+                return String::new();
+            }
+
+            let descriptor = &*(buffer.as_ptr() as *const STORAGE_DEVICE_DESCRIPTOR);
+            let offset = descriptor.ProductIdOffset as usize;
+            if offset == 0 || offset >= buffer.len() {
+                return String::new();
+            }
+            let end = buffer[offset..]
+                .iter()
+                .position(|&b| b == 0)
+                .map(|p| offset + p)
+                .unwrap_or(returned as usize);
+            return String::from_utf8_lossy(&buffer[offset..end]).trim().to_string();
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        if let Ok(output) = Command::new("diskutil").arg("info").arg(letter).output() {
+            if let Ok(stdout) = String::from_utf8(output.stdout) {
+                for line in stdout.lines() {
+                    let line = line.trim();
+                    if let Some(value) = line.strip_prefix("Device / Media Name:") {
+                        return value.trim().to_string();
+                    }
+                }
+            }
+        }
+        String::new()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let dev = linux_parent_block_device(letter);
+        let model_path = format!("/sys/block/{}/device/model", dev);
+        fs::read_to_string(&model_path)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        let _ = letter;
+        String::new()
+    }
+}
+
+/// Classify a drive as solid-state, rotating, removable, or unknown so the
+/// caller can avoid overwriting flash (where remapped cells survive a wipe).
+///
+/// Windows queries `StorageDeviceSeekPenaltyProperty` on the volume handle,
+/// macOS reads the IOKit media characteristics (`Solid State`) via diskutil,
+/// and Linux reads `/sys/block/<dev>/queue/rotational`.
+fn detect_disk_kind(letter: &str) -> String {
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, HANDLE};
+        use windows::Win32::Storage::FileSystem::{
+            CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+            OPEN_EXISTING,
+        };
+        use windows::Win32::System::Ioctl::{
+            DEVICE_SEEK_PENALTY_DESCRIPTOR, IOCTL_STORAGE_QUERY_PROPERTY, PropertyStandardQuery,
+            STORAGE_PROPERTY_QUERY, StorageDeviceSeekPenaltyProperty,
+        };
+        use windows::Win32::System::IO::DeviceIoControl;
+        use windows::core::{HSTRING, PCWSTR};
+
+        // `\\.\X:` addresses the volume without a trailing slash.
+        let drive = letter.trim_end_matches(['\\', '/']);
+        let device_path = HSTRING::from(format!("\\\\.\\{}", drive));
+
+        unsafe {
+            let handle: HANDLE = match CreateFileW(
+                PCWSTR::from_raw(device_path.as_ptr()),
+                GENERIC_READ.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                HANDLE::default(),
+            ) {
+                Ok(h) => h,
+                Err(_) => return "Unknown".to_string(),
+            };
+
+            let query = STORAGE_PROPERTY_QUERY {
+                PropertyId: StorageDeviceSeekPenaltyProperty,
+                QueryType: PropertyStandardQuery,
+                AdditionalParameters: [0],
+            };
+            let mut descriptor = DEVICE_SEEK_PENALTY_DESCRIPTOR::default();
+            let mut returned = 0u32;
+            let ok = DeviceIoControl(
+                handle,
+                IOCTL_STORAGE_QUERY_PROPERTY,
+                Some(&query as *const _ as *const _),
+                std::mem::size_of::<STORAGE_PROPERTY_QUERY>() as u32,
+                Some(&mut descriptor as *mut _ as *mut _),
+                std::mem::size_of::<DEVICE_SEEK_PENALTY_DESCRIPTOR>() as u32,
+                Some(&mut returned),
+                None,
+            );
+            let _ = CloseHandle(handle);
+
+            if ok.is_err() {
+                return "Unknown".to_string();
+            }
+            // No seek penalty => flash (SSD); a penalty => rotating platters.
+            if descriptor.IncursSeekPenalty.as_bool() {
+                return "HDD".to_string();
+            }
+            return "SSD".to_string();
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        if let Ok(output) = Command::new("diskutil").arg("info").arg(letter).output() {
+            if let Ok(stdout) = String::from_utf8(output.stdout) {
+                for line in stdout.lines() {
+                    let line = line.trim();
+                    // IOKit media characteristics surface as "Solid State: Yes/No".
+                    if let Some(value) = line.strip_prefix("Solid State:") {
+                        return if value.trim().eq_ignore_ascii_case("yes") {
+                            "SSD".to_string()
+                        } else {
+                            "HDD".to_string()
+                        };
+                    }
+                    if let Some(value) = line.strip_prefix("Removable Media:") {
+                        if value.trim().eq_ignore_ascii_case("removable") {
+                            return "Removable".to_string();
+                        }
+                    }
+                }
+            }
+        }
+        return "Unknown".to_string();
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // Walk a partition node up to its parent disk; rotational lives there.
+        let dev = linux_parent_block_device(letter);
+        let rotational = format!("/sys/block/{}/queue/rotational", dev);
+        if let Ok(contents) = fs::read_to_string(&rotational) {
+            return match contents.trim() {
+                "0" => "SSD".to_string(),
+                "1" => "HDD".to_string(),
+                _ => "Unknown".to_string(),
+            };
+        }
+        return "Unknown".to_string();
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        let _ = letter;
+        "Unknown".to_string()
+    }
 }
 
 #[derive(serde::Serialize)]
@@ -43,6 +355,24 @@ fn test_system_info() -> String {
     format!("System test successful - Rust backend working | OS: {} | Arch: {}", os, arch)
 }
 
+/// Total and available bytes for a mount point via `statvfs`, or `None` if the
+/// mount can't be measured (stale mount, permission, pseudo-filesystem).
+#[cfg(unix)]
+fn statvfs_size_free(mount: &str) -> Option<(u64, u64)> {
+    use std::ffi::CString;
+    let c_mount = CString::new(mount).ok()?;
+    unsafe {
+        let mut stat: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(c_mount.as_ptr(), &mut stat) != 0 {
+            return None;
+        }
+        let frsize = stat.f_frsize as u64;
+        let size = stat.f_blocks as u64 * frsize;
+        let free = stat.f_bavail as u64 * frsize;
+        Some((size, free))
+    }
+}
+
 #[command]
 fn detect_drives() -> Result<Vec<DriveInfo>, String> {
     let mut drives = Vec::new();
@@ -50,41 +380,54 @@ fn detect_drives() -> Result<Vec<DriveInfo>, String> {
     // On macOS, we'll detect mounted volumes
     #[cfg(target_os = "macos")]
     {
-        if let Ok(entries) = fs::read_dir("/Volumes") {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let drive_letter = path.file_name()
-                            .and_then(|name| name.to_str())
-                            .unwrap_or("Unknown")
-                            .to_string();
-                        
-                        let mut drive_info = DriveInfo {
-                            letter: format!("/Volumes/{}", drive_letter),
-                            size: 0,
-                            free_space: 0,
-                            label: drive_letter.clone(),
-                            file_system: "APFS/HFS+".to_string(),
-                            serial_number: 0,
-                            is_system: false,
-                        };
-                        
-                        // Get disk space info
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            // This is a simplified approach - in a real app you'd use statvfs
-                            drive_info.size = 0; // Would need statvfs for actual size
-                            drive_info.free_space = 0; // Would need statvfs for actual free space
-                        }
-                        
-                        // Check if it's the system drive
-                        if drive_letter == "Macintosh HD" || path.to_string_lossy().contains("Macintosh HD") {
-                            drive_info.is_system = true;
-                        }
-                        
-                        drives.push(drive_info);
-                    }
+        // Enumerate every mounted filesystem (not just /Volumes) via getmntinfo
+        // so the UI sees true sizes and filesystem types for each mount.
+        unsafe {
+            let mut mntbuf: *mut libc::statfs = std::ptr::null_mut();
+            let count = libc::getmntinfo(&mut mntbuf, libc::MNT_WAIT);
+            for i in 0..count as isize {
+                let entry = &*mntbuf.offset(i);
+
+                let mount = std::ffi::CStr::from_ptr(entry.f_mntonname.as_ptr())
+                    .to_string_lossy()
+                    .into_owned();
+                let device = std::ffi::CStr::from_ptr(entry.f_mntfromname.as_ptr())
+                    .to_string_lossy()
+                    .into_owned();
+                let fs_type = std::ffi::CStr::from_ptr(entry.f_fstypename.as_ptr())
+                    .to_string_lossy()
+                    .into_owned();
+
+                // Skip pseudo filesystems with no backing device.
+                if !device.starts_with('/') {
+                    continue;
                 }
+
+                let label = Path::new(&mount)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(&mount)
+                    .to_string();
+
+                let mut drive_info = DriveInfo {
+                    letter: mount.clone(),
+                    size: 0,
+                    free_space: 0,
+                    label,
+                    file_system: fs_type,
+                    serial_number: 0,
+                    is_system: mount == "/",
+                    disk_kind: detect_disk_kind(&mount),
+                    drive_type: "Unknown".to_string(),
+                    model: detect_model(&mount),
+                };
+
+                if let Some((size, free)) = statvfs_size_free(&mount) {
+                    drive_info.size = size;
+                    drive_info.free_space = free;
+                }
+
+                drives.push(drive_info);
             }
         }
     }
@@ -92,9 +435,23 @@ fn detect_drives() -> Result<Vec<DriveInfo>, String> {
     // On Windows, use the original Windows-specific code
     #[cfg(target_os = "windows")]
     {
-        use windows::Win32::Storage::FileSystem::{GetLogicalDrives, GetDiskFreeSpaceExW, GetVolumeInformationW};
+        use windows::Win32::Storage::FileSystem::{
+            GetLogicalDrives, GetDiskFreeSpaceExW, GetVolumeInformationW, GetDriveTypeW,
+        };
+        use windows::Win32::System::WindowsProgramming::{DRIVE_FIXED, DRIVE_REMOVABLE};
         use windows::core::{PCWSTR, HSTRING};
-        
+
+        // Map GetDriveTypeW to the coarse Fixed/Removable classification the UI
+        // needs; everything else (network, CD-ROM, RAM disk) is left Unknown.
+        let classify_drive_type = |root: &str| -> String {
+            let root = HSTRING::from(root);
+            match unsafe { GetDriveTypeW(PCWSTR::from_raw(root.as_ptr())) } {
+                DRIVE_FIXED => "Fixed".to_string(),
+                DRIVE_REMOVABLE => "Removable".to_string(),
+                _ => "Unknown".to_string(),
+            }
+        };
+
         unsafe {
             let drives_mask = GetLogicalDrives();
             if drives_mask == 0 {
@@ -112,6 +469,9 @@ fn detect_drives() -> Result<Vec<DriveInfo>, String> {
                         file_system: String::new(),
                         serial_number: 0,
                         is_system: false,
+                        disk_kind: detect_disk_kind(&drive_letter),
+                        drive_type: classify_drive_type(&drive_letter),
+                        model: detect_model(&drive_letter),
                     };
 
                     let mut label = [0u16; 256];
@@ -159,35 +519,132 @@ fn detect_drives() -> Result<Vec<DriveInfo>, String> {
                     drives.push(drive_info);
                 }
             }
-        }
-    }
-    
-    // On Linux, detect mounted filesystems
-    #[cfg(target_os = "linux")]
-    {
-        if let Ok(entries) = fs::read_dir("/mnt") {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let drive_letter = path.file_name()
-                            .and_then(|name| name.to_str())
-                            .unwrap_or("Unknown")
-                            .to_string();
-                        
-                        let mut drive_info = DriveInfo {
-                            letter: format!("/mnt/{}", drive_letter),
+
+            // Second pass: volumes with no drive letter (mount-point folders,
+            // hidden recovery/EFI partitions, freshly attached disks) never
+            // show up in GetLogicalDrives, so walk the volume GUID list too.
+            use windows::Win32::Storage::FileSystem::{
+                FindFirstVolumeW, FindNextVolumeW, FindVolumeClose,
+                GetVolumePathNamesForVolumeNameW,
+            };
+
+            let mut volume_name = [0u16; 260];
+            let find_handle = FindFirstVolumeW(&mut volume_name);
+            if let Ok(find_handle) = find_handle {
+                loop {
+                    let guid = String::from_utf16_lossy(&volume_name)
+                        .trim_end_matches('\0')
+                        .to_string();
+
+                    // Resolve every mount point this volume is exposed at.
+                    let mut names = [0u16; 1024];
+                    let mut returned = 0u32;
+                    let resolved = GetVolumePathNamesForVolumeNameW(
+                        PCWSTR::from_raw(volume_name.as_ptr()),
+                        Some(&mut names),
+                        &mut returned,
+                    );
+
+                    // Collect the NUL-separated, double-NUL-terminated paths.
+                    let mount_points: Vec<String> = if resolved.is_ok() {
+                        names[..returned as usize]
+                            .split(|&c| c == 0)
+                            .filter(|s| !s.is_empty())
+                            .map(String::from_utf16_lossy)
+                            .collect()
+                    } else {
+                        Vec::new()
+                    };
+
+                    // Skip volumes already surfaced by the drive-letter pass.
+                    let has_letter = mount_points.iter().any(|m| {
+                        let trimmed = m.trim_end_matches('\\');
+                        trimmed.len() == 2 && trimmed.ends_with(':')
+                    });
+
+                    if !has_letter {
+                        let label = mount_points
+                            .first()
+                            .cloned()
+                            .unwrap_or_else(|| guid.clone());
+                        // Query type on the volume GUID path (needs trailing \).
+                        let drive_type = classify_drive_type(&guid);
+
+                        drives.push(DriveInfo {
+                            letter: guid.clone(),
                             size: 0,
                             free_space: 0,
-                            label: drive_letter.clone(),
-                            file_system: "ext4".to_string(),
+                            label,
+                            file_system: String::new(),
                             serial_number: 0,
                             is_system: false,
-                        };
-                        
-                        drives.push(drive_info);
+                            disk_kind: detect_disk_kind(&guid),
+                            drive_type,
+                            model: detect_model(&guid),
+                        });
+                    }
+
+                    volume_name = [0u16; 260];
+                    if FindNextVolumeW(find_handle, &mut volume_name).is_err() {
+                        break;
                     }
                 }
+                let _ = FindVolumeClose(find_handle);
+            }
+        }
+    }
+
+    // On Linux, detect mounted filesystems
+    #[cfg(target_os = "linux")]
+    {
+        // Parse /proc/mounts for every real mount and its true filesystem type
+        // rather than guessing ext4 for whatever happens to live under /mnt.
+        if let Ok(contents) = fs::read_to_string("/proc/mounts") {
+            for line in contents.lines() {
+                let mut fields = line.split_whitespace();
+                let device = match fields.next() {
+                    Some(d) => d,
+                    None => continue,
+                };
+                let mount = match fields.next() {
+                    Some(m) => m,
+                    None => continue,
+                };
+                let fs_type = fields.next().unwrap_or("unknown");
+
+                // Only real block devices carry data worth sanitizing.
+                if !device.starts_with("/dev/") {
+                    continue;
+                }
+
+                // /proc/mounts octal-escapes spaces and similar in paths.
+                let mount = mount.replace("\\040", " ");
+
+                let label = Path::new(&mount)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(&mount)
+                    .to_string();
+
+                let mut drive_info = DriveInfo {
+                    letter: mount.clone(),
+                    size: 0,
+                    free_space: 0,
+                    label,
+                    file_system: fs_type.to_string(),
+                    serial_number: 0,
+                    is_system: mount == "/",
+                    disk_kind: detect_disk_kind(device),
+                    drive_type: "Unknown".to_string(),
+                    model: detect_model(device),
+                };
+
+                if let Some((size, free)) = statvfs_size_free(&mount) {
+                    drive_info.size = size;
+                    drive_info.free_space = free;
+                }
+
+                drives.push(drive_info);
             }
         }
     }
@@ -208,58 +665,1009 @@ fn check_safety(drive_letter: String) -> Result<bool, String> {
     }
 }
 
+/// How a single overwrite pass fills each block.
+enum PassPattern {
+    /// Repeat one fixed byte (e.g. 0x00 for NIST Clear, 0xFF, 0x55).
+    Fill(u8),
+    /// Tile a fixed multi-byte pattern across the block (Gutmann encodings).
+    Bytes(Vec<u8>),
+    /// Cryptographically irrelevant but unpredictable bytes.
+    Random,
+}
+
+/// One pass of a sanitization method. `verify` requests a post-pass read-back
+/// of the pass's first block to confirm the pattern actually landed.
+struct Pass {
+    pattern: PassPattern,
+    verify: bool,
+}
+
+/// Build the ordered pass list for a named overwrite standard.
+///
+/// Recognised methods: `NIST-800-88`, `DoD-5220.22-M`, `Gutmann`. Unknown
+/// methods are rejected rather than silently falling back to a weaker wipe.
+fn build_passes(method: &str) -> Result<Vec<Pass>, String> {
+    match method {
+        // NIST 800-88 Clear: a single zero pass, verified.
+        "NIST-800-88" => Ok(vec![Pass { pattern: PassPattern::Fill(0x00), verify: true }]),
+
+        // DoD 5220.22-M: a byte, its complement, then random, with a read-back
+        // verification of the final (random) pass.
+        "DoD-5220.22-M" => Ok(vec![
+            Pass { pattern: PassPattern::Fill(0x00), verify: false },
+            Pass { pattern: PassPattern::Fill(0xFF), verify: false },
+            Pass { pattern: PassPattern::Random, verify: true },
+        ]),
+
+        // Gutmann: 4 random, 27 fixed encoding patterns, 4 random.
+        "Gutmann" => {
+            let mut passes = Vec::with_capacity(35);
+            for _ in 0..4 {
+                passes.push(Pass { pattern: PassPattern::Random, verify: false });
+            }
+            // The classic 27 patterns covering the RLL/MFM encoding sequences.
+            let fixed: [&[u8]; 27] = [
+                &[0x55], &[0xAA], &[0x92, 0x49, 0x24], &[0x49, 0x24, 0x92],
+                &[0x24, 0x92, 0x49], &[0x00], &[0x11], &[0x22], &[0x33],
+                &[0x44], &[0x55], &[0x66], &[0x77], &[0x88], &[0x99],
+                &[0xAA], &[0xBB], &[0xCC], &[0xDD], &[0xEE], &[0xFF],
+                &[0x92, 0x49, 0x24], &[0x49, 0x24, 0x92], &[0x24, 0x92, 0x49],
+                &[0x6D, 0xB6, 0xDB], &[0xB6, 0xDB, 0x6D], &[0xDB, 0x6D, 0xB6],
+            ];
+            for pat in fixed {
+                passes.push(Pass { pattern: PassPattern::Bytes(pat.to_vec()), verify: false });
+            }
+            for _ in 0..4 {
+                passes.push(Pass { pattern: PassPattern::Random, verify: false });
+            }
+            Ok(passes)
+        }
+
+        other => Err(format!("Unknown sanitization method: {}", other)),
+    }
+}
+
+/// Fill `buffer` for one pass. A random pattern draws fresh bytes from `rng`
+/// on every call; fixed patterns produce the same bytes each time.
+fn fill_buffer(buffer: &mut [u8], pattern: &PassPattern, rng: &mut impl Rng) {
+    match pattern {
+        PassPattern::Fill(b) => buffer.fill(*b),
+        PassPattern::Bytes(pat) => {
+            for (i, slot) in buffer.iter_mut().enumerate() {
+                *slot = pat[i % pat.len()];
+            }
+        }
+        PassPattern::Random => rng.fill(buffer),
+    }
+}
+
+/// Cumulative read/write byte counters for a physical device, as reported by
+/// the OS. Sampling before and after a wipe lets the written-byte total be
+/// cross-checked against what the hardware actually saw.
+#[derive(Clone, Debug, serde::Serialize)]
+struct DiskIo {
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+/// Read the OS cumulative I/O counters for `device` (a `/dev` node, a bare
+/// block-device name, or a `\\.\PhysicalDriveN` path), or `None` if they can't
+/// be sampled on this platform/device.
+fn read_disk_io(device: &str) -> Option<DiskIo> {
+    #[cfg(target_os = "linux")]
+    {
+        // /proc/diskstats reports sectors; a sector is fixed at 512 bytes here.
+        let name = device.trim_end_matches('/').rsplit('/').next().unwrap_or(device);
+        let contents = fs::read_to_string("/proc/diskstats").ok()?;
+        for line in contents.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() >= 10 && fields[2] == name {
+                let sectors_read: u64 = fields[5].parse().ok()?;
+                let sectors_written: u64 = fields[9].parse().ok()?;
+                return Some(DiskIo {
+                    read_bytes: sectors_read * 512,
+                    write_bytes: sectors_written * 512,
+                });
+            }
+        }
+        None
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, HANDLE};
+        use windows::Win32::Storage::FileSystem::{
+            CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+            OPEN_EXISTING,
+        };
+        use windows::Win32::System::Ioctl::{DISK_PERFORMANCE, IOCTL_DISK_PERFORMANCE};
+        use windows::Win32::System::IO::DeviceIoControl;
+        use windows::core::{HSTRING, PCWSTR};
+
+        let device_path = if device.starts_with("\\\\.\\") {
+            device.to_string()
+        } else {
+            format!("\\\\.\\{}", device.trim_end_matches(['\\', '/']))
+        };
+        let wide = HSTRING::from(device_path);
+
+        unsafe {
+            let handle: HANDLE = CreateFileW(
+                PCWSTR::from_raw(wide.as_ptr()),
+                GENERIC_READ.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                HANDLE::default(),
+            )
+            .ok()?;
+
+            let mut perf = DISK_PERFORMANCE::default();
+            let mut returned = 0u32;
+            let ok = DeviceIoControl(
+                handle,
+                IOCTL_DISK_PERFORMANCE,
+                None,
+                0,
+                Some(&mut perf as *mut _ as *mut _),
+                std::mem::size_of::<DISK_PERFORMANCE>() as u32,
+                Some(&mut returned),
+                None,
+            );
+            let _ = CloseHandle(handle);
+            if ok.is_err() {
+                return None;
+            }
+            Some(DiskIo {
+                read_bytes: perf.BytesRead as u64,
+                write_bytes: perf.BytesWritten as u64,
+            })
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        // IOBlockStorageDriver exposes a Statistics dict (printed by ioreg as
+        // `"Bytes (Read)"=N` / `"Bytes (Write)"=N`); its child IOMedia carries
+        // the `"BSD Name"`. In a full `ioreg -l` dump the parent driver prints
+        // its Statistics just before the whole-disk media prints its BSD Name,
+        // so the most-recent counters seen before the matching name belong to
+        // the target disk — this is how we scope the sample per-device.
+        let whole = {
+            let name = device.trim_end_matches('/').rsplit('/').next().unwrap_or(device);
+            let name = name.strip_prefix('r').filter(|s| s.starts_with("disk")).unwrap_or(name);
+            match name.strip_prefix("disk") {
+                Some(rest) => {
+                    let digits: String =
+                        rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    format!("disk{}", digits)
+                }
+                None => name.to_string(),
+            }
+        };
+
+        let output = Command::new("ioreg").args(["-l", "-w0"]).output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let parse_value = |line: &str, key: &str| -> Option<u64> {
+            line.split_once(key).and_then(|(_, rest)| {
+                rest.trim_start_matches(['"', '=', ' '])
+                    .split(|c: char| !c.is_ascii_digit())
+                    .next()
+                    .filter(|s| !s.is_empty())
+                    .and_then(|n| n.parse().ok())
+            })
+        };
+
+        let bsd_marker = format!("\"BSD Name\" = \"{}\"", whole);
+        let mut pending_read = 0u64;
+        let mut pending_write = 0u64;
+        for line in stdout.lines() {
+            if let Some(v) = parse_value(line, "\"Bytes (Read)\"") {
+                pending_read = v;
+            }
+            if let Some(v) = parse_value(line, "\"Bytes (Write)\"") {
+                pending_write = v;
+            }
+            if line.contains(&bsd_marker) {
+                return Some(DiskIo { read_bytes: pending_read, write_bytes: pending_write });
+            }
+        }
+        None
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        let _ = device;
+        None
+    }
+}
+
+/// The backing block device for a mounted drive, for non-destructive I/O
+/// sampling (unlike `resolve_raw_device`, this never unmounts). The mount point
+/// itself matches nothing in `/proc/diskstats`, so the /dev node is needed.
+fn backing_device(drive_letter: &str) -> String {
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(contents) = fs::read_to_string("/proc/mounts") {
+            for line in contents.lines() {
+                let mut fields = line.split_whitespace();
+                let device = fields.next().unwrap_or("");
+                let mount = fields.next().unwrap_or("").replace("\\040", " ");
+                if mount == drive_letter && device.starts_with("/dev/") {
+                    return device.to_string();
+                }
+            }
+        }
+        drive_letter.to_string()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        if let Ok(output) = Command::new("diskutil").arg("info").arg(drive_letter).output() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if let Some(node) = stdout
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("Device Node:"))
+            {
+                return node.trim().to_string();
+            }
+        }
+        drive_letter.to_string()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        // On Windows the volume path (`\\.\X:`) is a valid IOCTL target.
+        drive_letter.to_string()
+    }
+}
+
+/// Issue the OS-native TRIM / secure-erase for flash media, where overwriting
+/// is both ineffective (wear-leveling leaves data on spare cells) and harmful.
+/// Linux uses `blkdiscard` on `device` (the mount's own /dev node, so a
+/// partition rather than the whole disk), macOS `diskutil secureErase`, and
+/// Windows a ReTrim via `Optimize-Volume`.
+#[allow(unused_variables)]
+fn ssd_secure_erase(drive_letter: &str, device: &str) -> Result<(), String> {
+    use std::process::Command;
+
+    #[cfg(target_os = "linux")]
+    {
+        // A plain discard (TRIM); `-f` skips blkdiscard's exclusive-open
+        // check, which would otherwise refuse the still-mounted device.
+        let status = Command::new("blkdiscard")
+            .arg("-f")
+            .arg(device)
+            .status()
+            .map_err(|e| format!("running blkdiscard: {}", e))?;
+        if !status.success() {
+            return Err(format!("blkdiscard failed on {}", device));
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // secureErase level 0 is a single-pass zero/TRIM appropriate for SSDs.
+        let status = Command::new("diskutil")
+            .args(["secureErase", "0", device])
+            .status()
+            .map_err(|e| format!("running diskutil secureErase: {}", e))?;
+        if !status.success() {
+            return Err(format!("diskutil secureErase failed on {}", device));
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // Drive letter like `X:\` -> `X`; Optimize-Volume -ReTrim issues TRIM.
+        let letter = drive_letter.trim_end_matches([':', '\\', '/']);
+        let status = Command::new("powershell")
+            .args([
+                "-Command",
+                &format!("Optimize-Volume -DriveLetter {} -ReTrim", letter),
+            ])
+            .status()
+            .map_err(|e| format!("running Optimize-Volume: {}", e))?;
+        if !status.success() {
+            return Err(format!("Optimize-Volume ReTrim failed on {}", drive_letter));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        Err("TRIM/secure-erase is not supported on this platform".to_string())
+    }
+}
+
+/// Format the write-byte delta between two I/O samples for the final report,
+/// or an empty string if either sample was unavailable.
+fn io_delta_summary(before: &Option<DiskIo>, after: &Option<DiskIo>) -> String {
+    match (before, after) {
+        (Some(b), Some(a)) => {
+            let delta = a.write_bytes.saturating_sub(b.write_bytes);
+            format!(
+                "; device I/O counter shows {} MB written",
+                delta / (1024 * 1024)
+            )
+        }
+        _ => String::new(),
+    }
+}
+
+/// The auditable body of an erasure certificate — everything a NIST 800-88
+/// "document the sanitization" record must carry. Hashed to detect tampering.
+#[derive(Clone, serde::Serialize)]
+struct CertificateBody {
+    asset_id: String,
+    method: String,
+    pass_count: u32,
+    verified: bool,
+    started_unix: u64,
+    finished_unix: u64,
+    // Device identity.
+    device: String,
+    label: String,
+    model: String,
+    serial_number: u32,
+    capacity_bytes: u64,
+    disk_kind: String,
+}
+
+/// A signed certificate of sanitization: the auditable body plus a SHA-256 over
+/// its canonical JSON so any later edit is detectable.
+#[derive(Clone, serde::Serialize)]
+struct ErasureCertificate {
+    body: CertificateBody,
+    sha256: String,
+}
+
+/// Seconds since the Unix epoch, or 0 if the clock is before it.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Build the certificate, hash its body, and write JSON + human-readable text
+/// to the temp directory. Returns the path of the JSON certificate.
+fn write_certificate(body: CertificateBody) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+
+    let canonical = serde_json::to_vec(&body).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    hasher.update(&canonical);
+    let sha256 = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+
+    let cert = ErasureCertificate { body: body.clone(), sha256: sha256.clone() };
+
+    let stem = format!(
+        "erasure-certificate-{}-{}",
+        body.asset_id.replace(['/', '\\', ' '], "_"),
+        body.finished_unix
+    );
+    let dir = std::env::temp_dir();
+    let json_path = dir.join(format!("{}.json", stem));
+    let text_path = dir.join(format!("{}.txt", stem));
+
+    let json = serde_json::to_string_pretty(&cert).map_err(|e| e.to_string())?;
+    fs::write(&json_path, &json).map_err(|e| e.to_string())?;
+
+    let text = format!(
+        "Certificate of Data Sanitization\n\
+         =================================\n\
+         Asset ID       : {}\n\
+         Device         : {} ({})\n\
+         Model          : {}\n\
+         Serial number  : {}\n\
+         Capacity       : {} bytes\n\
+         Disk kind      : {}\n\
+         Method         : {} ({} pass(es))\n\
+         Verified       : {}\n\
+         Started (unix) : {}\n\
+         Finished (unix): {}\n\
+         SHA-256        : {}\n",
+        body.asset_id,
+        body.device,
+        body.label,
+        body.model,
+        body.serial_number,
+        body.capacity_bytes,
+        body.disk_kind,
+        body.method,
+        body.pass_count,
+        body.verified,
+        body.started_unix,
+        body.finished_unix,
+        sha256,
+    );
+    fs::write(&text_path, text).map_err(|e| e.to_string())?;
+
+    Ok(json_path.to_string_lossy().into_owned())
+}
+
+/// Expose the OS cumulative I/O counters for a device to the frontend so a wipe
+/// can be cross-checked against the hardware's own view.
+#[command]
+fn get_disk_io(device: String) -> Result<DiskIo, String> {
+    read_disk_io(&device).ok_or_else(|| format!("No I/O counters available for {}", device))
+}
+
+/// Durable flush to the storage medium. `Write::flush` is a no-op for `File`
+/// and the raw device handle, so a pass must `sync_all` to actually push data
+/// to hardware before the verification read-back is meaningful.
+trait SyncToDevice {
+    fn sync_to_device(&self) -> std::io::Result<()>;
+}
+
+impl SyncToDevice for std::fs::File {
+    fn sync_to_device(&self) -> std::io::Result<()> {
+        self.sync_all()
+    }
+}
+
+/// Drive the configured passes over `pass_bytes` of `writer`, emitting
+/// `sanitize-progress` and honoring the cancellation token. Shared by the
+/// free-space (temp file) and raw block-device paths so both behave identically.
+///
+/// Returns `true` when a read-back verification was actually performed and
+/// passed, so callers can record an honest verification result rather than
+/// merely whether a verify pass was requested.
+fn run_passes<W: Write + std::io::Read + std::io::Seek + SyncToDevice>(
+    writer: &mut W,
+    pass_bytes: u64,
+    passes: &[Pass],
+    window: &tauri::Window,
+    drive: &str,
+    cancel: &AtomicBool,
+) -> Result<bool, String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut did_verify = false;
+
+    let buffer_size = 1024 * 1024usize; // 1MB buffer
+    let mut rng = rand::rng();
+    let pass_total = passes.len() as u32;
+    let total_bytes = pass_bytes * pass_total as u64;
+
+    // Moving-average throughput over the last handful of block writes.
+    let mut recent: std::collections::VecDeque<(Instant, u64)> =
+        std::collections::VecDeque::new();
+    let mut bytes_written: u64 = 0;
+    let mut buffer = vec![0u8; buffer_size];
+
+    for (index, pass) in passes.iter().enumerate() {
+        fill_buffer(&mut buffer, &pass.pattern, &mut rng);
+        if writer.seek(SeekFrom::Start(0)).is_err() {
+            // Not all targets are seekable; writing sequentially is still fine.
+        }
+
+        // Remember the first block actually written so a later read-back can be
+        // verified even for random passes (where the buffer changes per block).
+        let mut first_block: Vec<u8> = Vec::new();
+        let mut pass_written: u64 = 0;
+        while pass_written < pass_bytes {
+            // Honor a cancel request between writes so the wipe can be aborted
+            // without leaving the device handle mid-block.
+            if cancel.load(Ordering::SeqCst) {
+                return Err(format!("Sanitization of {} cancelled", drive));
+            }
+
+            let chunk = buffer_size.min((pass_bytes - pass_written) as usize);
+            if let PassPattern::Random = pass.pattern {
+                fill_buffer(&mut buffer, &pass.pattern, &mut rng);
+            }
+            if let Err(e) = writer.write_all(&buffer[..chunk]) {
+                return Err(e.to_string());
+            }
+            if pass_written == 0 {
+                first_block = buffer[..chunk].to_vec();
+            }
+            pass_written += chunk as u64;
+            bytes_written += chunk as u64;
+
+            let now = Instant::now();
+            recent.push_back((now, chunk as u64));
+            while recent.len() > 16 {
+                recent.pop_front();
+            }
+            let mb_per_sec = match (recent.front(), recent.back()) {
+                (Some((first, _)), Some((last, _))) if last > first => {
+                    let window_bytes: u64 = recent.iter().skip(1).map(|(_, b)| b).sum();
+                    let secs = last.duration_since(*first).as_secs_f64();
+                    if secs > 0.0 {
+                        (window_bytes as f64 / secs) / (1024.0 * 1024.0)
+                    } else {
+                        0.0
+                    }
+                }
+                _ => 0.0,
+            };
+
+            let _ = window.emit(
+                "sanitize-progress",
+                SanitizeProgress {
+                    drive: drive.to_string(),
+                    bytes_written,
+                    total_bytes,
+                    pass_index: index as u32 + 1,
+                    pass_total,
+                    percent: (bytes_written as f64 / total_bytes as f64) * 100.0,
+                    mb_per_sec,
+                },
+            );
+        }
+
+        // Push the pass to the medium before reading back: a bare flush leaves
+        // the data in the page cache, so the verify read would be satisfied
+        // from cache and could never catch a failed or remapped write. (A
+        // buffered read-back still hits cache on Linux; O_DIRECT or a cache
+        // drop is needed for a true hardware read, but sync_all at least
+        // guarantees the bytes left this process.)
+        if let Err(e) = writer.flush() {
+            return Err(e.to_string());
+        }
+        if let Err(e) = writer.sync_to_device() {
+            return Err(e.to_string());
+        }
+
+        // Read back the first block and confirm it matches what we wrote. This
+        // works for random passes too (DoD's final pass) because we compare
+        // against the exact bytes written, not a regenerated pattern.
+        if pass.verify && !first_block.is_empty() {
+            if let Err(e) = writer.seek(SeekFrom::Start(0)) {
+                return Err(e.to_string());
+            }
+            let mut read_back = vec![0u8; first_block.len()];
+            if let Err(e) = writer.read_exact(&mut read_back) {
+                return Err(e.to_string());
+            }
+            if read_back != first_block {
+                return Err(format!(
+                    "Verification failed on {}: written pattern did not read back",
+                    drive
+                ));
+            }
+            did_verify = true;
+        }
+    }
+
+    Ok(did_verify)
+}
+
+/// Resolve the raw block device to open for a given drive. Returns the OS path
+/// to `CreateFileW`/`open` on. On Windows a supplied physical-device index
+/// selects `\\.\PhysicalDriveN` directly and is not checked against
+/// `drive_letter`; on Linux and macOS the index is not used. macOS unmounts the
+/// disk first (raw `/dev/rdiskN` requires the volume not be mounted).
+#[allow(unused_variables)]
+fn resolve_raw_device(
+    drive_letter: &str,
+    physical_device_index: Option<u32>,
+) -> Result<String, String> {
+    #[cfg(target_os = "windows")]
+    {
+        // A confirmed physical index targets the whole disk; otherwise fall
+        // back to the volume path (`\\.\X:`).
+        if let Some(index) = physical_device_index {
+            return Ok(format!("\\\\.\\PhysicalDrive{}", index));
+        }
+        let volume = drive_letter.trim_end_matches(['\\', '/']);
+        Ok(format!("\\\\.\\{}", volume))
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // Map the mount point back to its /dev node via /proc/mounts, then walk
+        // up to the parent whole disk so the wipe covers the partition table,
+        // sibling partitions, and inter-partition slack — not just one mount.
+        let contents = fs::read_to_string("/proc/mounts")
+            .map_err(|e| format!("reading /proc/mounts: {}", e))?;
+        for line in contents.lines() {
+            let mut fields = line.split_whitespace();
+            let device = fields.next().unwrap_or("");
+            let mount = fields.next().unwrap_or("").replace("\\040", " ");
+            if mount == drive_letter && device.starts_with("/dev/") {
+                return Ok(format!("/dev/{}", linux_parent_block_device(device)));
+            }
+        }
+        Err(format!("No block device found for mount {}", drive_letter))
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        // Find the /dev node backing this mount, then switch to the raw
+        // character device and unmount so we can write it exclusively.
+        let info = Command::new("diskutil")
+            .arg("info")
+            .arg(drive_letter)
+            .output()
+            .map_err(|e| format!("diskutil info: {}", e))?;
+        let stdout = String::from_utf8_lossy(&info.stdout);
+        let node = stdout
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("Device Node:"))
+            .map(|v| v.trim().to_string())
+            .ok_or_else(|| format!("No device node for {}", drive_letter))?;
+
+        Command::new("diskutil")
+            .arg("unmountDisk")
+            .arg(&node)
+            .output()
+            .map_err(|e| format!("diskutil unmountDisk: {}", e))?;
+
+        // /dev/diskN -> /dev/rdiskN (raw, unbuffered).
+        Ok(node.replacen("/dev/disk", "/dev/rdisk", 1))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    {
+        Err("Raw device wiping is not supported on this platform".to_string())
+    }
+}
+
+/// True length in bytes of a raw block device, so a whole-disk wipe covers the
+/// entire device (including the tail and other partitions) rather than stopping
+/// at a single filesystem's reported size.
+fn raw_device_length(device: &str) -> Option<u64> {
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, HANDLE};
+        use windows::Win32::Storage::FileSystem::{
+            CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+            OPEN_EXISTING,
+        };
+        use windows::Win32::System::Ioctl::{GET_LENGTH_INFORMATION, IOCTL_DISK_GET_LENGTH_INFO};
+        use windows::Win32::System::IO::DeviceIoControl;
+        use windows::core::{HSTRING, PCWSTR};
+
+        let wide = HSTRING::from(device);
+        unsafe {
+            let handle: HANDLE = CreateFileW(
+                PCWSTR::from_raw(wide.as_ptr()),
+                GENERIC_READ.0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                HANDLE::default(),
+            )
+            .ok()?;
+            let mut info = GET_LENGTH_INFORMATION::default();
+            let mut returned = 0u32;
+            let ok = DeviceIoControl(
+                handle,
+                IOCTL_DISK_GET_LENGTH_INFO,
+                None,
+                0,
+                Some(&mut info as *mut _ as *mut _),
+                std::mem::size_of::<GET_LENGTH_INFORMATION>() as u32,
+                Some(&mut returned),
+                None,
+            );
+            let _ = CloseHandle(handle);
+            if ok.is_err() {
+                return None;
+            }
+            Some(info.Length as u64)
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // /sys/class/block/<node>/size is in 512-byte sectors, for both whole
+        // disks and partitions.
+        let name = device.trim_end_matches('/').rsplit('/').next().unwrap_or(device);
+        let size_path = format!("/sys/class/block/{}/size", name);
+        let sectors: u64 = fs::read_to_string(&size_path).ok()?.trim().parse().ok()?;
+        Some(sectors * 512)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        // diskutil reports `Disk Size: 500.3 GB (500277790720 Bytes ...)`.
+        let output = Command::new("diskutil").arg("info").arg(device).output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            let line = line.trim();
+            if line.starts_with("Disk Size:") || line.starts_with("Total Size:") {
+                if let Some(open) = line.find('(') {
+                    let rest = &line[open + 1..];
+                    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    if let Ok(bytes) = digits.parse::<u64>() {
+                        return Some(bytes);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    {
+        let _ = device;
+        None
+    }
+}
+
+/// Physical drive numbers backing a Windows volume (e.g. `C:`), via
+/// `IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS`. Used to keep a confirmed raw
+/// physical-device index from pointing at the disk that hosts a system volume.
+#[cfg(target_os = "windows")]
+fn volume_physical_drives(volume: &str) -> Vec<u32> {
+    use windows::Win32::Foundation::{CloseHandle, GENERIC_READ, HANDLE};
+    use windows::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+    };
+    use windows::Win32::System::Ioctl::{
+        IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS, VOLUME_DISK_EXTENTS,
+    };
+    use windows::Win32::System::IO::DeviceIoControl;
+    use windows::core::{HSTRING, PCWSTR};
+
+    let trimmed = volume.trim_end_matches(['\\', '/']);
+    let wide = HSTRING::from(format!("\\\\.\\{}", trimmed));
+    let mut drives = Vec::new();
+
+    unsafe {
+        let handle: HANDLE = match CreateFileW(
+            PCWSTR::from_raw(wide.as_ptr()),
+            GENERIC_READ.0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            HANDLE::default(),
+        ) {
+            Ok(h) => h,
+            Err(_) => return drives,
+        };
+
+        // Over-allocate for volumes spanning several disks (spanned/striped).
+        let mut buffer = [0u8; 1024];
+        let mut returned = 0u32;
+        let ok = DeviceIoControl(
+            handle,
+            IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS,
+            None,
+            0,
+            Some(buffer.as_mut_ptr() as *mut _),
+            buffer.len() as u32,
+            Some(&mut returned),
+            None,
+        );
+        let _ = CloseHandle(handle);
+        if ok.is_err() {
+            return drives;
+        }
+
+        let extents = &*(buffer.as_ptr() as *const VOLUME_DISK_EXTENTS);
+        let count = extents.NumberOfDiskExtents as usize;
+        for i in 0..count {
+            drives.push(extents.Extents.as_ptr().add(i).read_unaligned().DiskNumber);
+        }
+    }
+
+    drives
+}
+
+/// Request that an in-flight wipe of `drive_letter` stop at the next block
+/// boundary. A no-op if nothing is running for that drive.
+#[command]
+fn cancel_sanitize(drive_letter: String) -> Result<(), String> {
+    let registry = cancel_registry().lock().unwrap();
+    if let Some(token) = registry.get(&drive_letter) {
+        token.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
 #[command]
-async fn sanitize_drive(drive_letter: String, confirm: bool) -> Result<String, String> {
+async fn sanitize_drive(
+    window: tauri::Window,
+    drive_letter: String,
+    method: String,
+    raw: bool,
+    physical_device_index: Option<u32>,
+    asset_id: String,
+    confirm: bool,
+) -> Result<String, String> {
     if !confirm {
         return Err("Confirmation required to proceed".to_string());
     }
-    
+
+    let passes = build_passes(&method)?;
+
     let drives = detect_drives()?;
     if let Some(drive) = drives.iter().find(|d| d.letter == drive_letter) {
         if drive.is_system {
             return Err("Cannot sanitize system drive".to_string());
         }
-        
+
         let path = drive_letter.clone();
         if !Path::new(&path).exists() {
             return Err(format!("Drive {} not found or not accessible", path));
         }
-        
-        // Create a temporary file for sanitization
-        let temp_file_path = format!("{}/temp_sanitize_file", path);
-        let mut file = match fs::File::create(&temp_file_path) {
-            Ok(f) => f,
-            Err(e) => return Err(e.to_string()),
-        };
-        
-        let buffer_size = 1024 * 1024; // 1MB buffer
-        let mut rng = rand::rng();
-        let iterations = 100; // Limit to 100 iterations for safety
-        
-        for pass_num in 0..3 { // 3-pass overwrite (zeros, ones, random)
-            let buffer = match pass_num {
-                0 => vec![0u8; buffer_size],  // Pass 1: Zeros
-                1 => vec![255u8; buffer_size], // Pass 2: Ones
-                _ => (0..buffer_size).map(|_| rng.random()).collect(), // Pass 3: Random
-            };
-            
-            for _ in 0..iterations {
-                if let Err(e) = file.write_all(&buffer) {
-                    return Err(e.to_string());
-                }
+
+        // Flash media remaps blocks under the filesystem, so a multi-pass
+        // overwrite neither erases the spare cells nor is kind to the NAND.
+        // Sanitize SSDs with a single TRIM/secure-erase instead, and certify it.
+        if drive.disk_kind == "SSD" {
+            let device = backing_device(&path);
+            let started = unix_now();
+            ssd_secure_erase(&path, &device)?;
+            let finished = unix_now();
+
+            let cert_path = write_certificate(CertificateBody {
+                asset_id: asset_id.clone(),
+                method: "TRIM/secure-erase".to_string(),
+                pass_count: 1,
+                verified: false,
+                started_unix: started,
+                finished_unix: finished,
+                device: device.clone(),
+                label: drive.label.clone(),
+                model: drive.model.clone(),
+                serial_number: drive.serial_number,
+                capacity_bytes: drive.size,
+                disk_kind: drive.disk_kind.clone(),
+            })?;
+
+            return Ok(format!(
+                "Sanitized SSD {} via TRIM/secure-erase; certificate: {}",
+                path, cert_path
+            ));
+        }
+
+        let buffer_size = 1024 * 1024u64; // 1MB buffer
+        let pass_total = passes.len() as u32;
+        let cancel = cancel_token(&path);
+
+        if raw {
+            // Raw mode overwrites the block device directly: existing files,
+            // filesystem metadata, and slack space are all erased, unlike a
+            // temp file that only covers currently-free space. Because it's
+            // irreversible and unguarded by the filesystem, demand an explicit
+            // physical-device index confirmation so it can't fire by accident.
+            if physical_device_index.is_none() {
+                return Err(
+                    "Raw device wiping requires an explicit physical device index \
+                     to confirm the destructive target"
+                        .to_string(),
+                );
             }
-            
-            if let Err(e) = file.sync_all() {
-                return Err(e.to_string());
+
+            // The physical index is decoupled from `drive_letter`, so the
+            // earlier is_system check on the found drive is not enough: a
+            // non-system letter paired with the boot disk's index would wipe
+            // the OS. Reject any index that backs a system volume.
+            #[cfg(target_os = "windows")]
+            {
+                if let Some(index) = physical_device_index {
+                    for sys in drives.iter().filter(|d| d.is_system) {
+                        if volume_physical_drives(&sys.letter).contains(&index) {
+                            return Err(format!(
+                                "Physical drive {} backs the system volume {} and \
+                                 cannot be wiped",
+                                index, sys.letter
+                            ));
+                        }
+                    }
+                }
             }
+
+            let device = resolve_raw_device(&path, physical_device_index)?;
+            // Raw mode spans the whole device: take the real device length so
+            // the tail of the disk and sibling partitions aren't left intact.
+            let pass_bytes = raw_device_length(&device)
+                .unwrap_or(drive.size)
+                .max(buffer_size);
+            let io_before = read_disk_io(&device);
+            let mut handle = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&device)
+                .map_err(|e| format!("Opening raw device {}: {}", device, e))?;
+
+            let started = unix_now();
+            let verified = run_passes(&mut handle, pass_bytes, &passes, &window, &path, &cancel)?;
+            // Ensure every write has reached the device before reading its
+            // counters, or the delta could undercount what was just written.
+            let _ = handle.sync_all();
+            let finished = unix_now();
+            let io_after = read_disk_io(&device);
+
+            let cert_path = write_certificate(CertificateBody {
+                asset_id: asset_id.clone(),
+                method: method.clone(),
+                pass_count: pass_total,
+                verified,
+                started_unix: started,
+                finished_unix: finished,
+                device: device.clone(),
+                label: drive.label.clone(),
+                model: drive.model.clone(),
+                serial_number: drive.serial_number,
+                capacity_bytes: pass_bytes,
+                disk_kind: drive.disk_kind.clone(),
+            })?;
+
+            return Ok(format!(
+                "Sanitized raw device {} with {} ({} pass(es), {} MB per pass){}; certificate: {}",
+                device,
+                method,
+                pass_total,
+                pass_bytes / (1024 * 1024),
+                io_delta_summary(&io_before, &io_after),
+                cert_path
+            ));
         }
-        
-        // Clean up the temporary file
-        if let Err(e) = fs::remove_file(&temp_file_path) {
-            return Err(e.to_string());
-        }
-        
-        Ok(format!("Sanitized {} with 3-pass overwrite method (limited to {}MB)", path, iterations * (buffer_size as u64) / (1024 * 1024)))
+
+        // Free-space mode: overwrite via a temp file on the mounted filesystem.
+        // Size to *free* space, not total capacity — the temp file lives on the
+        // filesystem being wiped, so writing `size` bytes always hits ENOSPC.
+        let pass_bytes = drive.free_space.max(buffer_size);
+        let temp_file_path = format!("{}/temp_sanitize_file", path);
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&temp_file_path)
+            .map_err(|e| e.to_string())?;
+
+        // Sample the backing device's counters, not the mount point (which
+        // matches nothing in the OS I/O tables).
+        let io_device = backing_device(&path);
+        let io_before = read_disk_io(&io_device);
+        let started = unix_now();
+        let result = run_passes(&mut file, pass_bytes, &passes, &window, &path, &cancel);
+        // Flush to the device before sampling counters so the delta reflects
+        // the writes rather than data still sitting in cache.
+        let _ = file.sync_all();
+
+        // Clean up the temporary file regardless of the wipe outcome.
+        let _ = fs::remove_file(&temp_file_path);
+        let verified = result?;
+        let finished = unix_now();
+        let io_after = read_disk_io(&io_device);
+
+        let cert_path = write_certificate(CertificateBody {
+            asset_id: asset_id.clone(),
+            method: method.clone(),
+            pass_count: pass_total,
+            verified,
+            started_unix: started,
+            finished_unix: finished,
+            device: path.clone(),
+            label: drive.label.clone(),
+            model: drive.model.clone(),
+            serial_number: drive.serial_number,
+            capacity_bytes: drive.size,
+            disk_kind: drive.disk_kind.clone(),
+        })?;
+
+        Ok(format!(
+            "Sanitized {} with {} ({} pass(es), {} MB per pass){}; certificate: {}",
+            path,
+            method,
+            pass_total,
+            pass_bytes / (1024 * 1024),
+            io_delta_summary(&io_before, &io_after),
+            cert_path
+        ))
     } else {
         Err("Drive not found".to_string())
     }
@@ -323,7 +1731,92 @@ fn get_system_specs() -> SystemSpecs {
 
 fn main() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![test_system_info, detect_drives, check_safety, sanitize_drive, greet, get_system_specs])
+        .invoke_handler(tauri::generate_handler![test_system_info, detect_drives, check_safety, sanitize_drive, cancel_sanitize, get_disk_io, greet, get_system_specs])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nist_is_single_verified_zero_pass() {
+        let passes = build_passes("NIST-800-88").unwrap();
+        assert_eq!(passes.len(), 1);
+        assert!(matches!(passes[0].pattern, PassPattern::Fill(0x00)));
+        assert!(passes[0].verify);
+    }
+
+    #[test]
+    fn dod_is_byte_complement_random_with_final_verify() {
+        let passes = build_passes("DoD-5220.22-M").unwrap();
+        assert_eq!(passes.len(), 3);
+        assert!(matches!(passes[0].pattern, PassPattern::Fill(0x00)));
+        assert!(matches!(passes[1].pattern, PassPattern::Fill(0xFF)));
+        assert!(matches!(passes[2].pattern, PassPattern::Random));
+        // Only the final pass carries the read-back verification.
+        assert!(!passes[0].verify && !passes[1].verify && passes[2].verify);
+    }
+
+    #[test]
+    fn gutmann_is_35_passes_in_4_27_4_order() {
+        let passes = build_passes("Gutmann").unwrap();
+        assert_eq!(passes.len(), 35);
+        for p in &passes[0..4] {
+            assert!(matches!(p.pattern, PassPattern::Random));
+        }
+        for p in &passes[4..31] {
+            assert!(matches!(p.pattern, PassPattern::Bytes(_)));
+        }
+        for p in &passes[31..35] {
+            assert!(matches!(p.pattern, PassPattern::Random));
+        }
+    }
+
+    #[test]
+    fn unknown_method_is_rejected() {
+        assert!(build_passes("rot13").is_err());
+    }
+
+    #[test]
+    fn fill_buffer_tiles_multibyte_pattern() {
+        let mut rng = rand::rng();
+        let mut buf = [0u8; 7];
+        fill_buffer(&mut buf, &PassPattern::Bytes(vec![0x92, 0x49, 0x24]), &mut rng);
+        assert_eq!(buf, [0x92, 0x49, 0x24, 0x92, 0x49, 0x24, 0x92]);
+    }
+
+    #[test]
+    fn fill_buffer_fills_single_byte() {
+        let mut rng = rand::rng();
+        let mut buf = [0u8; 4];
+        fill_buffer(&mut buf, &PassPattern::Fill(0x55), &mut rng);
+        assert_eq!(buf, [0x55, 0x55, 0x55, 0x55]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parent_block_device_strips_partition_suffix() {
+        assert_eq!(linux_parent_block_device("/dev/sda1"), "sda");
+        assert_eq!(linux_parent_block_device("/dev/nvme0n1p2"), "nvme0n1");
+        assert_eq!(linux_parent_block_device("/dev/mmcblk0p1"), "mmcblk0");
+    }
+
+    #[test]
+    fn io_delta_summary_reports_write_delta() {
+        let before = Some(DiskIo { read_bytes: 0, write_bytes: 1024 * 1024 });
+        let after = Some(DiskIo { read_bytes: 0, write_bytes: 5 * 1024 * 1024 });
+        assert_eq!(
+            io_delta_summary(&before, &after),
+            "; device I/O counter shows 4 MB written"
+        );
+    }
+
+    #[test]
+    fn io_delta_summary_empty_when_unsampled() {
+        let some = Some(DiskIo { read_bytes: 0, write_bytes: 1 });
+        assert_eq!(io_delta_summary(&None, &some), "");
+        assert_eq!(io_delta_summary(&some, &None), "");
+    }
 }
\ No newline at end of file